@@ -1,6 +1,15 @@
 pub use kurbo;
 use kurbo::{Point, Rect, Vec2, PathEl, BezPath, ParamCurve};
 
+pub mod svg;
+pub use svg::{parse_svg_path, path_to_svg, ParseError};
+
+pub mod stroke;
+pub use stroke::{stroke_path, LineCap, LineJoin, StrokeStyle};
+
+mod simd;
+pub use simd::break_path_batch;
+
 #[derive(Debug, Copy, Clone)]
 pub enum CubicApprox {
     //
@@ -9,61 +18,201 @@ pub enum CubicApprox {
     Flatten(f64),
     // Fast but rough approximation (1-2 quads)
     Midpoint,
-    // Generates quadratic segments
-    Lyon(f64),
+    // Generates quadratic segments via kurbo's analytic subdivision-count
+    // estimate, rather than recursive flattening
+    Kurbo(f64),
+    // Adaptively subdivides until each quad is within `tolerance` of the
+    // source cubic, measured as a true geometric deviation
+    Recursive(f64),
 }
 
-pub fn break_path(orig: &BezPath, cubic_approx: CubicApprox) -> BezPath {
-    let mut initial = Point::ORIGIN;
-    let mut p0 = Point::ORIGIN;
-    let mut path = BezPath::new();
+// Split quadratic at parametric point t.
+//
+// Returns new control points and midpoint.
+fn quad_split_point(t: f64, p0: Point, p1: Point, p2: Point) -> [Point; 3] {
+    let pa = p0.lerp(p1, t);
+    let pc = p1.lerp(p2, t);
+    let pb = pa.lerp(pc, t);
+
+    [pa, pb, pc]
+}
+
+// Axis-extrema split parameters for `add_quad`'s quad, shared by the scalar
+// path and the batched `simd` path so both stay in lockstep.
+fn quad_split_params(p0: Point, p1: Point, p2: Point) -> (Option<f64>, Option<f64>) {
+    let aabb = Rect::from_points(p0, p1);
+    let tx = if aabb.min_x() > p1.x || p1.x > aabb.max_x() {
+        Some((p0.x - p1.x) / (p0.x - 2.0 * p1.x + p2.x))
+    } else {
+        None
+    };
+    let ty = if aabb.min_y() > p1.y || p1.y > aabb.max_y() {
+        Some((p0.y - p1.y) / (p0.y - 2.0 * p1.y + p2.y))
+    } else {
+        None
+    };
+    (tx, ty)
+}
+
+fn emit_quad(path: &mut BezPath, p0: Point, p1: Point, p2: Point, params: (Option<f64>, Option<f64>)) {
+    match params {
+        (Some(tx), Some(ty)) => {
+            let t0 = tx.min(ty);
+            let t1 = (tx.max(ty) - t0) / (1.0 - t0);
 
-    fn add_quad(path: &mut BezPath, p0: Point, p1: Point, p2: Point) {
-        // Split quadratic at parametric point t
-        //
-        // Returns new control points and midpoint
-        fn split(t: f64, p0: Point, p1: Point, p2: Point) -> [Point; 3] {
-            let pa = p0.lerp(p1, t);
-            let pc = p1.lerp(p2, t);
-            let pb = pa.lerp(pc, t);
+            let [pa0, pb0, pc0] = quad_split_point(t0, p0, p1, p2);
+            let [pa1, pb1, pc1] = quad_split_point(t1, pb0, pc0, p2);
 
-            [pa, pb, pc]
+            path.quad_to(pa0, pb0);
+            path.quad_to(pa1, pb1);
+            path.quad_to(pc1, p2);
         }
+        (Some(t), None) | (None, Some(t)) => {
+            let [pa, pb, pc] = quad_split_point(t, p0, p1, p2);
+            path.quad_to(pa, pb);
+            path.quad_to(pc, p2);
+        }
+        (None, None) => {
+            path.quad_to(p1, p2);
+        }
+    }
+}
 
-        let aabb = Rect::from_points(p0, p1);
-        let tx = if aabb.min_x() > p1.x || p1.x > aabb.max_x() {
-            Some((p0.x - p1.x) / (p0.x - 2.0 * p1.x + p2.x))
-        } else {
-            None
-        };
-        let ty = if aabb.min_y() > p1.y || p1.y > aabb.max_y() {
-            Some((p0.y - p1.y) / (p0.y - 2.0 * p1.y + p2.y))
-        } else {
-            None
-        };
+fn add_quad(path: &mut BezPath, p0: Point, p1: Point, p2: Point) {
+    emit_quad(path, p0, p1, p2, quad_split_params(p0, p1, p2));
+}
 
-        match (tx, ty) {
-            (Some(tx), Some(ty)) => {
-                let t0 = tx.min(ty);
-                let t1 = (tx.max(ty) - t0) / (1.0 - t0);
+// Approximates a cubic by a single quad sharing its endpoints, recursing
+// (de Casteljau at t=0.5) until the quad's deviation from the cubic is
+// within `tolerance`, or `depth` is exhausted.
+fn approx_cubic_recursive(
+    path: &mut BezPath,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    tolerance: f64,
+    depth: u32,
+) {
+    let q = (p1.to_vec2() * 3.0 + p2.to_vec2() * 3.0 - p0.to_vec2() - p3.to_vec2()) / 4.0;
+    let e = (3.0_f64.sqrt() / 36.0) * ((p3 - p2) - (p2 - p1) * 2.0 + (p1 - p0)).hypot();
 
-                let [pa0, pb0, pc0] = split(t0, p0, p1, p2);
-                let [pa1, pb1, pc1] = split(t1, pb0, pc0, p2);
+    if e <= tolerance || depth == 0 {
+        add_quad(path, p0, q.to_point(), p3);
+    } else {
+        let p01 = p0.lerp(p1, 0.5);
+        let p12 = p1.lerp(p2, 0.5);
+        let p23 = p2.lerp(p3, 0.5);
+        let p012 = p01.lerp(p12, 0.5);
+        let p123 = p12.lerp(p23, 0.5);
+        let p0123 = p012.lerp(p123, 0.5);
 
-                path.quad_to(pa0, pb0);
-                path.quad_to(pa1, pb1);
-                path.quad_to(pc1, p2);
-            }
-            (Some(t), None) | (None, Some(t)) => {
-                let [pa, pb, pc] = split(t, p0, p1, p2);
-                path.quad_to(pa, pb);
-                path.quad_to(pc, p2);
+        approx_cubic_recursive(path, p0, p01, p012, p0123, tolerance, depth - 1);
+        approx_cubic_recursive(path, p0123, p123, p23, p3, tolerance, depth - 1);
+    }
+}
+
+// Applies one `CurveTo` element to `path` per `cubic_approx`. `p0` is passed
+// by value (the caller tracks the running current point); `Flatten` reports
+// the points it emits through `on_point` so the caller can keep that point
+// in sync too.
+fn apply_cubic_approx(
+    path: &mut BezPath,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    cubic_approx: CubicApprox,
+    mut on_point: impl FnMut(Point),
+) {
+    match cubic_approx {
+        CubicApprox::Linear => {
+            path.line_to(p3);
+        }
+        CubicApprox::Flatten(tolerance) => {
+            let mut subpath = BezPath::new();
+            subpath.move_to(p0);
+            subpath.curve_to(p1, p2, p3);
+            kurbo::flatten(subpath, tolerance, |el| match el {
+                PathEl::MoveTo(_) => {}
+                PathEl::LineTo(p) => {
+                    path.line_to(p);
+                    on_point(p);
+                }
+                _ => unreachable!(),
+            });
+        }
+        CubicApprox::Midpoint => {
+            // 3.5 Alternative approximation of cubic curves
+            if p0 == p1 {
+                add_quad(path, p0, p2, p3);
+            } else if p2 == p3 {
+                add_quad(path, p0, p1, p3);
+            } else {
+                let p_ca = p0.lerp(p1, 0.75);
+                let p_cb = p3.lerp(p2, 0.75);
+                let p_m = p_ca.midpoint(p_cb);
+                add_quad(path, p0, p_ca, p_m);
+                add_quad(path, p_m, p_cb, p3);
             }
-            (None, None) => {
-                path.quad_to(p1, p2);
+        }
+        CubicApprox::Kurbo(accuracy) => {
+            // Closed-form subdivision count, see Raph Levien's
+            // "flattening quadratic beziers" note: the number of
+            // quads needed is derived from the magnitude of the
+            // cubic's deviation from a quadratic, without any
+            // recursive error estimation.
+            let p1x2 = p1.to_vec2() * 3.0 - p0.to_vec2();
+            let p2x2 = p2.to_vec2() * 3.0 - p3.to_vec2();
+            let err = (p2x2 - p1x2).hypot2();
+            let n = ((err / (432.0 * accuracy * accuracy)).powf(1.0 / 6.0))
+                .ceil()
+                .max(1.0) as usize;
+
+            let mut c0 = p0;
+            let mut c1 = p1;
+            let mut c2 = p2;
+            let c3 = p3;
+            for i in 0..n {
+                let (sub0, sub1, sub2, sub3) = if i + 1 == n {
+                    (c0, c1, c2, c3)
+                } else {
+                    // Peel off the first 1/(n - i) of the
+                    // remaining tail via de Casteljau.
+                    let t = 1.0 / (n - i) as f64;
+                    let p01 = c0.lerp(c1, t);
+                    let p12 = c1.lerp(c2, t);
+                    let p23 = c2.lerp(c3, t);
+                    let p012 = p01.lerp(p12, t);
+                    let p123 = p12.lerp(p23, t);
+                    let p0123 = p012.lerp(p123, t);
+
+                    let sub = (c0, p01, p012, p0123);
+                    c0 = p0123;
+                    c1 = p123;
+                    c2 = p23;
+                    sub
+                };
+
+                let q = (sub1.to_vec2() * 3.0 + sub2.to_vec2() * 3.0
+                    - sub0.to_vec2()
+                    - sub3.to_vec2())
+                    / 4.0;
+                add_quad(path, sub0, q.to_point(), sub3);
             }
         }
+        CubicApprox::Recursive(tolerance) => {
+            // Recursion depth 32 bounds pathological input (e.g.
+            // a cusp) to at most 2^32 quads worth of work.
+            approx_cubic_recursive(path, p0, p1, p2, p3, tolerance, 32);
+        }
     }
+}
+
+pub fn break_path(orig: &BezPath, cubic_approx: CubicApprox) -> BezPath {
+    let mut initial = Point::ORIGIN;
+    let mut p0 = Point::ORIGIN;
+    let mut path = BezPath::new();
 
     for elem in orig {
         match elem {
@@ -81,64 +230,7 @@ pub fn break_path(orig: &BezPath, cubic_approx: CubicApprox) -> BezPath {
                 p0 = p2;
             }
             PathEl::CurveTo(p1, p2, p3) => {
-                match cubic_approx {
-                    CubicApprox::Linear => {
-                        path.line_to(p3);
-                    }
-                    CubicApprox::Flatten(tolerance) => {
-                        let mut subpath = BezPath::new();
-                        subpath.move_to(p0);
-                        subpath.curve_to(p1, p2, p3);
-                        kurbo::flatten(subpath, tolerance, |el| match el {
-                            PathEl::MoveTo(_) => {}
-                            PathEl::LineTo(p) => {
-                                path.line_to(p);
-                                p0 = p;
-                            }
-                            _ => unreachable!(),
-                        });
-                    }
-                    CubicApprox::Midpoint => {
-                        // 3.5 Alternative approximation of cubic curves
-                        if p0 == p1 {
-                            add_quad(&mut path, p0, p2, p3);
-                        } else if p2 == p3 {
-                            add_quad(&mut path, p0, p1, p3);
-                        } else {
-                            let p_ca = p0.lerp(p1, 0.75);
-                            let p_cb = p3.lerp(p2, 0.75);
-                            let p_m = p_ca.midpoint(p_cb);
-                            add_quad(&mut path, p0, p_ca, p_m);
-                            add_quad(&mut path, p_m, p_cb, p3);
-                        }
-                    }
-                    CubicApprox::Lyon(tolerance) => {
-                        use lyon_geom::{
-                            cubic_bezier::CubicBezierSegment,
-                            cubic_to_quadratic::cubic_to_quadratics,
-                        };
-
-                        // monotonic variant appears to be buggy (v0.15)
-                        cubic_to_quadratics(
-                            &CubicBezierSegment {
-                                from: [p0.x, p0.y].into(),
-                                ctrl1: [p1.x, p1.y].into(),
-                                ctrl2: [p2.x, p2.y].into(),
-                                to: [p3.x, p3.y].into(),
-                            },
-                            tolerance,
-                            &mut |segment| {
-                                add_quad(
-                                    &mut path,
-                                    Point::new(segment.from.x, segment.from.y),
-                                    Point::new(segment.ctrl.x, segment.ctrl.y),
-                                    Point::new(segment.to.x, segment.to.y),
-                                );
-                            },
-                        );
-                    }
-                }
-
+                apply_cubic_approx(&mut path, p0, p1, p2, p3, cubic_approx, |p| p0 = p);
                 p0 = p3;
             }
             PathEl::ClosePath => {
@@ -200,6 +292,70 @@ pub fn monotonize_quads(orig: &BezPath) -> BezPath {
         }
     }
 
+    // Splits a cubic at its x- and y-extrema so every emitted sub-cubic is
+    // monotonic in both axes, matching the guarantee `split_quad` gives
+    // quadratics.
+    fn monotonize_cubic(path: &mut BezPath, p0: Point, p1: Point, p2: Point, p3: Point) {
+        // Roots in (0, 1) of the derivative of one axis of the cubic, given
+        // as start/ctrl1/ctrl2/end values `a,b,c,d`.
+        fn axis_roots(a: f64, b: f64, c: f64, d: f64, roots: &mut Vec<f64>) {
+            let aa = d - 3.0 * c + 3.0 * b - a;
+            let bb = 2.0 * (a - 2.0 * b + c);
+            let cc = b - a;
+
+            let mut push_root = |t: f64| {
+                if t > 0.0 && t < 1.0 {
+                    roots.push(t);
+                }
+            };
+
+            if aa.abs() < 1e-9 {
+                // Near-linear derivative: fall back to the single linear root.
+                if bb.abs() > 1e-9 {
+                    push_root(-cc / bb);
+                }
+                return;
+            }
+
+            let disc = bb * bb - 4.0 * aa * cc;
+            if disc < 0.0 {
+                return;
+            }
+            let sq = disc.sqrt();
+            push_root((-bb - sq) / (2.0 * aa));
+            push_root((-bb + sq) / (2.0 * aa));
+        }
+
+        let mut splits = Vec::new();
+        axis_roots(p0.x, p1.x, p2.x, p3.x, &mut splits);
+        axis_roots(p0.y, p1.y, p2.y, p3.y, &mut splits);
+        splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        splits.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+
+        let (mut c0, mut c1, mut c2, c3) = (p0, p1, p2, p3);
+        let mut last = 0.0;
+
+        for t in splits {
+            let local_t = (t - last) / (1.0 - last);
+
+            let p01 = c0.lerp(c1, local_t);
+            let p12 = c1.lerp(c2, local_t);
+            let p23 = c2.lerp(c3, local_t);
+            let p012 = p01.lerp(p12, local_t);
+            let p123 = p12.lerp(p23, local_t);
+            let p0123 = p012.lerp(p123, local_t);
+
+            path.curve_to(p01, p012, p0123);
+
+            c0 = p0123;
+            c1 = p123;
+            c2 = p23;
+            last = t;
+        }
+
+        path.curve_to(c1, c2, c3);
+    }
+
     for elem in orig {
         match elem {
             PathEl::MoveTo(p) => {
@@ -216,8 +372,7 @@ pub fn monotonize_quads(orig: &BezPath) -> BezPath {
                 p0 = p2;
             }
             PathEl::CurveTo(p1, p2, p3) => {
-                // quads only
-                path.curve_to(p1, p2, p3);
+                monotonize_cubic(&mut path, p0, p1, p2, p3);
                 p0 = p3;
             }
             PathEl::ClosePath => {
@@ -229,3 +384,126 @@ pub fn monotonize_quads(orig: &BezPath) -> BezPath {
 
     path
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Regression test for the review note that monotonize_cubic's
+    // quadratic-root splitting shipped without a single test: this cubic's
+    // x-derivative has two roots in (0, 1) (p1/p2 bulge well past both
+    // endpoints), so the old pass-through behavior would emit a single
+    // non-monotonic CurveTo. Assert it's split into pieces that are each
+    // monotonic in x, by sampling each emitted sub-cubic.
+    #[test]
+    fn monotonize_cubic_splits_bulging_curve_into_monotonic_pieces() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.curve_to(Point::new(30.0, 0.0), Point::new(-20.0, 0.0), Point::new(10.0, 0.0));
+
+        let out = monotonize_quads(&path);
+
+        let mut p0 = Point::ORIGIN;
+        let mut pieces = 0;
+        for el in &out {
+            match el {
+                PathEl::MoveTo(p) => p0 = p,
+                PathEl::CurveTo(p1, p2, p3) => {
+                    pieces += 1;
+                    let cubic = kurbo::CubicBez::new(p0, p1, p2, p3);
+                    let xs: Vec<f64> = (0..=8).map(|i| cubic.eval(i as f64 / 8.0).x).collect();
+                    let nondecreasing = xs.windows(2).all(|w| w[1] >= w[0] - 1e-9);
+                    let nonincreasing = xs.windows(2).all(|w| w[1] <= w[0] + 1e-9);
+                    assert!(nondecreasing || nonincreasing, "piece not monotonic in x: {xs:?}");
+                    p0 = p3;
+                }
+                _ => {}
+            }
+        }
+        assert!(pieces > 1, "expected the bulge to be split into multiple pieces, got {pieces}");
+    }
+
+    #[test]
+    fn kurbo_variant_reduces_straight_cubic_to_one_quad() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.curve_to(Point::new(1.0, 0.0), Point::new(2.0, 0.0), Point::new(3.0, 0.0));
+
+        let out = break_path(&path, CubicApprox::Kurbo(0.1));
+        let quads = (&out).into_iter().filter(|el| matches!(el, PathEl::QuadTo(..))).count();
+        assert_eq!(quads, 1);
+    }
+
+    #[test]
+    fn kurbo_variant_subdivides_high_curvature_cubic() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.curve_to(Point::new(0.0, 100.0), Point::new(100.0, 100.0), Point::new(100.0, 0.0));
+
+        let out = break_path(&path, CubicApprox::Kurbo(0.01));
+        let quads: Vec<_> = (&out).into_iter().filter(|el| matches!(el, PathEl::QuadTo(..))).collect();
+        assert!(quads.len() > 1, "expected multiple quads for a tight tolerance, got {}", quads.len());
+        for el in &quads {
+            if let PathEl::QuadTo(p1, p2) = el {
+                assert!(p1.x.is_finite() && p1.y.is_finite());
+                assert!(p2.x.is_finite() && p2.y.is_finite());
+            }
+        }
+    }
+
+    // Mild curve and a generous tolerance: the deviation formula should
+    // decide a single quad is already within `tolerance`, and that quad's
+    // actual sampled distance from the source cubic should stay within it.
+    #[test]
+    fn recursive_variant_single_quad_stays_within_tolerance() {
+        let p0 = Point::new(0.0, 0.0);
+        let p1 = Point::new(0.0, 50.0);
+        let p2 = Point::new(100.0, 50.0);
+        let p3 = Point::new(100.0, 0.0);
+        let tolerance = 10.0;
+
+        let mut path = BezPath::new();
+        path.move_to(p0);
+        path.curve_to(p1, p2, p3);
+
+        let out = break_path(&path, CubicApprox::Recursive(tolerance));
+        let quads: Vec<_> = (&out).into_iter().filter(|el| matches!(el, PathEl::QuadTo(..))).collect();
+        assert_eq!(quads.len(), 1, "expected this mild curve to stop at a single quad");
+
+        let PathEl::QuadTo(q1, q2) = quads[0] else { unreachable!() };
+        let quad = kurbo::QuadBez::new(p0, q1, q2);
+        let cubic = kurbo::CubicBez::new(p0, p1, p2, p3);
+        let max_dev = (0..=8)
+            .map(|i| {
+                let t = i as f64 / 8.0;
+                (quad.eval(t) - cubic.eval(t)).hypot()
+            })
+            .fold(0.0_f64, f64::max);
+        assert!(max_dev <= tolerance, "deviation {max_dev} exceeds tolerance {tolerance}");
+    }
+
+    // A self-intersecting cusp shape forces repeated recursion; assert it
+    // terminates (doesn't hang against the depth-32 cap) and every emitted
+    // point is finite.
+    #[test]
+    fn recursive_variant_terminates_on_cusp() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.curve_to(Point::new(10.0, 0.0), Point::new(-10.0, 0.0), Point::new(0.0, 0.0));
+
+        let out = break_path(&path, CubicApprox::Recursive(0.01));
+        let quads = (&out).into_iter().filter(|el| matches!(el, PathEl::QuadTo(..))).count();
+        assert!(quads >= 1);
+        for el in &out {
+            let pts: Vec<Point> = match el {
+                PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+                PathEl::QuadTo(p1, p2) => vec![p1, p2],
+                PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+                PathEl::ClosePath => vec![],
+            };
+            for p in pts {
+                assert!(p.x.is_finite() && p.y.is_finite());
+            }
+        }
+    }
+}
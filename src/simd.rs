@@ -0,0 +1,177 @@
+//! Batch API for processing many quads at once, e.g. when breaking every
+//! glyph outline in a font. With the `simd` feature enabled, the
+//! axis-extrema split-parameter computation that `add_quad` performs per
+//! quad is instead done four quads at a time using `wide`'s `f64x4` lanes.
+//! Without the feature, and for the quads left over when a run of
+//! consecutive `QuadTo` elements is not a multiple of four, the same scalar
+//! split computation that [`crate::break_path`] uses is applied per quad.
+
+use crate::{apply_cubic_approx, emit_quad, CubicApprox};
+use kurbo::{BezPath, PathEl, Point};
+
+#[cfg(feature = "simd")]
+mod wide_quad {
+    use kurbo::Point;
+    use wide::{f64x4, CmpGt, CmpLt};
+
+    // Batched version of `quad_split_params`: given four quads' `p0`/`p1`/`p2`
+    // packed into SIMD lanes, returns each lane's (tx, ty) split parameters.
+    //
+    // The bounding box here is deliberately built from `p0`/`p1` (not
+    // `p0`/`p2`), matching `crate::quad_split_params` exactly so this path
+    // stays bit-for-bit in lockstep with the scalar one.
+    pub(super) fn quad_split_params_x4(
+        p0: [Point; 4],
+        p1: [Point; 4],
+        p2: [Point; 4],
+    ) -> [(Option<f64>, Option<f64>); 4] {
+        let p0x = f64x4::from([p0[0].x, p0[1].x, p0[2].x, p0[3].x]);
+        let p0y = f64x4::from([p0[0].y, p0[1].y, p0[2].y, p0[3].y]);
+        let p1x = f64x4::from([p1[0].x, p1[1].x, p1[2].x, p1[3].x]);
+        let p1y = f64x4::from([p1[0].y, p1[1].y, p1[2].y, p1[3].y]);
+        let p2x = f64x4::from([p2[0].x, p2[1].x, p2[2].x, p2[3].x]);
+        let p2y = f64x4::from([p2[0].y, p2[1].y, p2[2].y, p2[3].y]);
+
+        let two = f64x4::splat(2.0);
+        let min_x = p0x.min(p1x);
+        let max_x = p0x.max(p1x);
+        let min_y = p0y.min(p1y);
+        let max_y = p0y.max(p1y);
+
+        let out_of_x = (p1x.cmp_lt(min_x) | p1x.cmp_gt(max_x)).to_array();
+        let out_of_y = (p1y.cmp_lt(min_y) | p1y.cmp_gt(max_y)).to_array();
+        let tx = ((p0x - p1x) / (p0x - p1x * two + p2x)).to_array();
+        let ty = ((p0y - p1y) / (p0y - p1y * two + p2y)).to_array();
+
+        std::array::from_fn(|i| {
+            (
+                (out_of_x[i] != 0.0).then_some(tx[i]),
+                (out_of_y[i] != 0.0).then_some(ty[i]),
+            )
+        })
+    }
+}
+
+// Drains every pending quad into `path`: complete groups of four go through
+// the SIMD batch path when the `simd` feature is enabled, and whatever's
+// left (fewer than four, or all of them without the feature) takes the
+// scalar path that `break_path` itself uses.
+fn flush_batch(path: &mut BezPath, pending: &mut Vec<(Point, Point, Point)>) {
+    #[cfg(feature = "simd")]
+    while pending.len() >= 4 {
+        let chunk: [(Point, Point, Point); 4] = std::array::from_fn(|i| pending[i]);
+        let params = wide_quad::quad_split_params_x4(
+            [chunk[0].0, chunk[1].0, chunk[2].0, chunk[3].0],
+            [chunk[0].1, chunk[1].1, chunk[2].1, chunk[3].1],
+            [chunk[0].2, chunk[1].2, chunk[2].2, chunk[3].2],
+        );
+        for (i, (p0, p1, p2)) in chunk.into_iter().enumerate() {
+            emit_quad(path, p0, p1, p2, params[i]);
+        }
+        pending.drain(0..4);
+    }
+
+    for (p0, p1, p2) in pending.drain(..) {
+        emit_quad(path, p0, p1, p2, crate::quad_split_params(p0, p1, p2));
+    }
+}
+
+fn break_path_one(orig: &BezPath, cubic_approx: CubicApprox) -> BezPath {
+    let mut initial = Point::ORIGIN;
+    let mut p0 = Point::ORIGIN;
+    let mut path = BezPath::new();
+    let mut pending: Vec<(Point, Point, Point)> = Vec::with_capacity(4);
+
+    for elem in orig {
+        match elem {
+            PathEl::MoveTo(p) => {
+                flush_batch(&mut path, &mut pending);
+                path.move_to(p);
+                p0 = p;
+                initial = p;
+            }
+            PathEl::LineTo(p) => {
+                flush_batch(&mut path, &mut pending);
+                path.line_to(p);
+                p0 = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                pending.push((p0, p1, p2));
+                p0 = p2;
+                if pending.len() == 4 {
+                    flush_batch(&mut path, &mut pending);
+                }
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                flush_batch(&mut path, &mut pending);
+                apply_cubic_approx(&mut path, p0, p1, p2, p3, cubic_approx, |p| p0 = p);
+                p0 = p3;
+            }
+            PathEl::ClosePath => {
+                flush_batch(&mut path, &mut pending);
+                path.close_path();
+                p0 = initial;
+            }
+        }
+    }
+    flush_batch(&mut path, &mut pending);
+    path
+}
+
+/// Applies [`crate::break_path`] to each path in `paths`. With the `simd`
+/// feature enabled, runs of direct `QuadTo` elements within a path are
+/// processed four at a time (see the module docs); output is identical to
+/// calling `break_path` on each path individually either way.
+pub fn break_path_batch(paths: &[BezPath], cubic_approx: CubicApprox) -> Vec<BezPath> {
+    paths
+        .iter()
+        .map(|path| break_path_one(path, cubic_approx))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    // Regression test for the divergence caught in review: four consecutive
+    // `QuadTo`s whose control point sits outside the `p0`-`p2` span used to
+    // go through a different axis-split test in the SIMD path than in the
+    // scalar one, breaking them into a different number of elements.
+    #[test]
+    fn batch_matches_individual_break_path() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        for _ in 0..4 {
+            path.quad_to(Point::new(20.0, 0.0), Point::new(10.0, 0.0));
+        }
+
+        let batched = break_path_batch(std::slice::from_ref(&path), CubicApprox::Linear);
+        let individual = crate::break_path(&path, CubicApprox::Linear);
+
+        let batched_els: Vec<_> = (&batched[0]).into_iter().collect();
+        let individual_els: Vec<_> = (&individual).into_iter().collect();
+        assert_eq!(batched_els, individual_els);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_params_match_scalar() {
+        let quads = [
+            (Point::new(0.0, 0.0), Point::new(20.0, 0.0), Point::new(10.0, 0.0)),
+            (Point::new(0.0, 0.0), Point::new(5.0, 5.0), Point::new(10.0, 0.0)),
+            (Point::new(0.0, 0.0), Point::new(-3.0, 7.0), Point::new(4.0, 4.0)),
+            (Point::new(1.0, 1.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)),
+        ];
+
+        let simd_params = wide_quad::quad_split_params_x4(
+            [quads[0].0, quads[1].0, quads[2].0, quads[3].0],
+            [quads[0].1, quads[1].1, quads[2].1, quads[3].1],
+            [quads[0].2, quads[1].2, quads[2].2, quads[3].2],
+        );
+
+        for (i, (p0, p1, p2)) in quads.into_iter().enumerate() {
+            assert_eq!(simd_params[i], crate::quad_split_params(p0, p1, p2));
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Stroke-to-fill: turns a stroked centerline into a closed outline that can
+//! be filled, so strokes can go through the same [`crate::break_path`] /
+//! [`crate::monotonize_quads`] pipeline as fills.
+
+use kurbo::{BezPath, PathEl, Point, Vec2};
+
+#[derive(Debug, Copy, Clone)]
+pub enum LineJoin {
+    Miter(f64),
+    Round,
+    Bevel,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct StrokeStyle {
+    pub width: f64,
+    pub join: LineJoin,
+    pub cap: LineCap,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Seg {
+    Line(Point, Point),
+    Quad(Point, Point, Point),
+    Cubic(Point, Point, Point, Point),
+}
+
+impl Seg {
+    fn start(&self) -> Point {
+        match *self {
+            Seg::Line(p0, _) => p0,
+            Seg::Quad(p0, ..) => p0,
+            Seg::Cubic(p0, ..) => p0,
+        }
+    }
+
+    fn end(&self) -> Point {
+        match *self {
+            Seg::Line(_, p1) => p1,
+            Seg::Quad(_, _, p2) => p2,
+            Seg::Cubic(_, _, _, p3) => p3,
+        }
+    }
+
+    fn tangent_start(&self) -> Vec2 {
+        match *self {
+            Seg::Line(p0, p1) => p1 - p0,
+            Seg::Quad(p0, p1, p2) => if p1 != p0 { p1 - p0 } else { p2 - p0 },
+            Seg::Cubic(p0, p1, p2, p3) => {
+                if p1 != p0 {
+                    p1 - p0
+                } else if p2 != p0 {
+                    p2 - p0
+                } else {
+                    p3 - p0
+                }
+            }
+        }
+    }
+
+    fn tangent_end(&self) -> Vec2 {
+        match *self {
+            Seg::Line(p0, p1) => p1 - p0,
+            Seg::Quad(p0, p1, p2) => if p2 != p1 { p2 - p1 } else { p2 - p0 },
+            Seg::Cubic(p0, p1, p2, p3) => {
+                if p3 != p2 {
+                    p3 - p2
+                } else if p3 != p1 {
+                    p3 - p1
+                } else {
+                    p3 - p0
+                }
+            }
+        }
+    }
+
+    fn reverse(&self) -> Seg {
+        match *self {
+            Seg::Line(p0, p1) => Seg::Line(p1, p0),
+            Seg::Quad(p0, p1, p2) => Seg::Quad(p2, p1, p0),
+            Seg::Cubic(p0, p1, p2, p3) => Seg::Cubic(p3, p2, p1, p0),
+        }
+    }
+
+    // Offsets the segment by signed distance `d` along its normal. Endpoints
+    // are moved along their own tangent's normal; interior control points
+    // move along the chord's normal, which is an approximation (exact offset
+    // curves of a conic aren't themselves polynomial) in the same spirit as
+    // `CubicApprox::Midpoint`.
+    fn offset(&self, d: f64) -> Seg {
+        let normal = |t: Vec2| -> Vec2 { safe_normal(t, d) };
+        match *self {
+            Seg::Line(p0, p1) => {
+                let n = normal(p1 - p0);
+                Seg::Line(p0 + n, p1 + n)
+            }
+            Seg::Quad(p0, p1, p2) => {
+                let n0 = normal(self.tangent_start());
+                let n2 = normal(self.tangent_end());
+                let nm = normal(p2 - p0);
+                Seg::Quad(p0 + n0, p1 + nm, p2 + n2)
+            }
+            Seg::Cubic(p0, p1, p2, p3) => {
+                let n0 = normal(self.tangent_start());
+                let n3 = normal(self.tangent_end());
+                let nm = normal(p3 - p0);
+                Seg::Cubic(p0 + n0, p1 + nm, p2 + nm, p3 + n3)
+            }
+        }
+    }
+}
+
+fn normalize(v: Vec2) -> Vec2 {
+    let h = v.hypot();
+    if h < 1e-9 {
+        Vec2::ZERO
+    } else {
+        v / h
+    }
+}
+
+// `t` rotated a quarter turn and scaled to length `d`, or the zero vector if
+// `t` itself is degenerate (a zero-length or otherwise collapsed segment) —
+// guards the divide that would otherwise send NaN points into the output.
+fn safe_normal(t: Vec2, d: f64) -> Vec2 {
+    let h = t.hypot();
+    if h < 1e-9 {
+        Vec2::ZERO
+    } else {
+        Vec2::new(-t.y, t.x) / h * d
+    }
+}
+
+fn line_intersect(p1: Point, d1: Vec2, p2: Point, d2: Vec2) -> Option<Point> {
+    let denom = d1.x * d2.y - d1.y * d2.x;
+    if denom.abs() < 1e-9 {
+        return None;
+    }
+    let t = ((p2.x - p1.x) * d2.y - (p2.y - p1.y) * d2.x) / denom;
+    Some(p1 + d1 * t)
+}
+
+// A fan of quads approximating a circular arc of radius `r` centered at
+// `center`, from `a` to `b`, split into <=90 degree steps (each step's quad
+// control point is the intersection of the arc's tangents at its ends).
+fn arc_segments(center: Point, a: Point, b: Point, r: f64) -> Vec<Seg> {
+    let mut start_angle = (a - center).atan2();
+    let end_angle = (b - center).atan2();
+
+    let cross = (a - center).x * (b - center).y - (a - center).y * (b - center).x;
+    let mut delta = end_angle - start_angle;
+    while delta <= -std::f64::consts::PI {
+        delta += std::f64::consts::TAU;
+    }
+    while delta > std::f64::consts::PI {
+        delta -= std::f64::consts::TAU;
+    }
+    if cross > 0.0 && delta < 0.0 {
+        delta += std::f64::consts::TAU;
+    } else if cross < 0.0 && delta > 0.0 {
+        delta -= std::f64::consts::TAU;
+    }
+
+    let segments = (delta.abs() / std::f64::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = delta / segments as f64;
+
+    let mut out = Vec::with_capacity(segments);
+    let mut prev = a;
+    for i in 0..segments {
+        let theta_end = start_angle + step;
+        let p_end = if i + 1 == segments {
+            b
+        } else {
+            center + Vec2::new(theta_end.cos(), theta_end.sin()) * r
+        };
+
+        let t1 = Vec2::new(-start_angle.sin(), start_angle.cos());
+        let t2 = Vec2::new(-theta_end.sin(), theta_end.cos());
+        let ctrl = line_intersect(prev, t1, p_end, t2).unwrap_or_else(|| prev.midpoint(p_end));
+
+        out.push(Seg::Quad(prev, ctrl, p_end));
+        prev = p_end;
+        start_angle = theta_end;
+    }
+    out
+}
+
+// Per-vertex geometry `build_join` needs, bundled into one struct (rather
+// than passed as individual arguments) so the function stays under clippy's
+// argument-count limit as this grows. `turn` is the cross product of the
+// original (un-offset, forward-traversal) path's tangents at this vertex,
+// used together with the rail's offset distance to tell a convex corner
+// (which opens a gap that needs join geometry) from a concave one (whose
+// offsets already overlap, so a plain connecting line is enough). `turn`
+// must always come from the forward traversal direction, even when building
+// the rail that's stitched together backwards, since the sign of a
+// tangent-to-tangent cross product flips under reversal but the convexity of
+// a given rail at a given vertex does not.
+struct JoinGeometry {
+    pivot: Point,
+    a: Point,
+    b: Point,
+    tangent_a: Vec2,
+    tangent_b: Vec2,
+    turn: f64,
+}
+
+fn build_join(d: f64, geom: JoinGeometry, join: LineJoin) -> Vec<Seg> {
+    let JoinGeometry { pivot, a, b, tangent_a, tangent_b, turn } = geom;
+
+    if a == b {
+        return Vec::new();
+    }
+
+    let da = normalize(tangent_a);
+    let db = normalize(tangent_b);
+    let convex = turn * d < 0.0;
+
+    if !convex {
+        // Concave corner: the two offset lines already cross just past the
+        // pivot, so trimming to that intersection (rather than connecting
+        // `a` and `b` directly) is what keeps the inner offset from
+        // overshooting the true corner into a self-intersecting notch.
+        return match line_intersect(a, da, b, db) {
+            Some(m) => vec![Seg::Line(a, m), Seg::Line(m, b)],
+            None => vec![Seg::Line(a, b)],
+        };
+    }
+
+    match join {
+        LineJoin::Bevel => vec![Seg::Line(a, b)],
+        LineJoin::Round => arc_segments(pivot, a, b, d.abs()),
+        LineJoin::Miter(limit) => match line_intersect(a, da, b, db) {
+            Some(m) if (m - pivot).hypot() <= limit * d.abs() => {
+                vec![Seg::Line(a, m), Seg::Line(m, b)]
+            }
+            _ => vec![Seg::Line(a, b)],
+        },
+    }
+}
+
+fn build_cap(pivot: Point, tangent: Vec2, a: Point, b: Point, hw: f64, cap: LineCap) -> Vec<Seg> {
+    match cap {
+        LineCap::Butt => vec![Seg::Line(a, b)],
+        LineCap::Square => {
+            let ext = normalize(tangent) * hw;
+            vec![
+                Seg::Line(a, a + ext),
+                Seg::Line(a + ext, b + ext),
+                Seg::Line(b + ext, b),
+            ]
+        }
+        LineCap::Round => {
+            // Two quarter arcs through the point straight ahead of the end, so
+            // the half circle bulges outward rather than back into the stroke.
+            let tip = pivot + normalize(tangent) * hw;
+            let mut segs = arc_segments(pivot, a, tip, hw);
+            segs.extend(arc_segments(pivot, tip, b, hw));
+            segs
+        }
+    }
+}
+
+// Per-vertex turn direction of the *forward* path traversal: `turn[i]` is the
+// cross product of the tangents meeting at the vertex between `segs[i]` and
+// `segs[(i + 1) % segs.len()]`. Shared by both rails of a subpath so that
+// convexity is judged consistently regardless of which rail is being
+// stitched together backwards.
+fn forward_turns(segs: &[Seg]) -> Vec<f64> {
+    let n = segs.len();
+    (0..n)
+        .map(|i| {
+            let a = segs[i].tangent_end();
+            let b = segs[(i + 1) % n].tangent_start();
+            a.x * b.y - a.y * b.x
+        })
+        .collect()
+}
+
+// Remaps a `forward_turns` table to the index order `join_rail` sees when
+// walking a rail built from the reversed-and-reversed `rorig`/`rsegs`
+// arrays: join `i` there lands on the same vertex as forward join
+// `(n - 2 - i) mod n`.
+fn reverse_turns(turn: &[f64]) -> Vec<f64> {
+    let n = turn.len() as isize;
+    (0..n)
+        .map(|i| turn[(n - 2 - i).rem_euclid(n) as usize])
+        .collect()
+}
+
+fn join_rail(
+    offset_segs: &[Seg],
+    original_segs: &[Seg],
+    turn: &[f64],
+    d: f64,
+    join: LineJoin,
+    wrap: bool,
+) -> Vec<Seg> {
+    let n = offset_segs.len();
+    let mut out = Vec::with_capacity(n * 2);
+    for i in 0..n {
+        out.push(offset_segs[i]);
+        if i + 1 < n || wrap {
+            let j = (i + 1) % n;
+            let pivot = original_segs[i].end();
+            let a = offset_segs[i].end();
+            let b = offset_segs[j].start();
+            out.extend(build_join(
+                d,
+                JoinGeometry {
+                    pivot,
+                    a,
+                    b,
+                    tangent_a: original_segs[i].tangent_end(),
+                    tangent_b: original_segs[j].tangent_start(),
+                    turn: turn[i],
+                },
+                join,
+            ));
+        }
+    }
+    out
+}
+
+fn emit_contour(out: &mut BezPath, segs: &[Seg]) {
+    if segs.is_empty() {
+        return;
+    }
+    out.move_to(segs[0].start());
+    for seg in segs {
+        match *seg {
+            Seg::Line(_, p1) => out.line_to(p1),
+            Seg::Quad(_, p1, p2) => out.quad_to(p1, p2),
+            Seg::Cubic(_, p1, p2, p3) => out.curve_to(p1, p2, p3),
+        }
+    }
+    out.close_path();
+}
+
+// Splits `path` into per-subpath `Seg` lists plus a closed flag. Segments
+// that collapse to a single point (consecutive duplicate points, which real
+// paths do contain) are dropped here rather than offset later, since a
+// zero-length segment has no well-defined tangent to offset along.
+fn subpaths(path: &BezPath) -> Vec<(Vec<Seg>, bool)> {
+    let mut result = Vec::new();
+    let mut current = Vec::new();
+    let mut p0 = Point::ORIGIN;
+    let mut start = Point::ORIGIN;
+    let mut closed = false;
+
+    for el in path {
+        match el {
+            PathEl::MoveTo(p) => {
+                if !current.is_empty() {
+                    result.push((std::mem::take(&mut current), closed));
+                }
+                closed = false;
+                p0 = p;
+                start = p;
+            }
+            PathEl::LineTo(p) => {
+                if p != p0 {
+                    current.push(Seg::Line(p0, p));
+                }
+                p0 = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                if p1 != p0 || p2 != p0 {
+                    current.push(Seg::Quad(p0, p1, p2));
+                }
+                p0 = p2;
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                if p1 != p0 || p2 != p0 || p3 != p0 {
+                    current.push(Seg::Cubic(p0, p1, p2, p3));
+                }
+                p0 = p3;
+            }
+            PathEl::ClosePath => {
+                if p0 != start {
+                    current.push(Seg::Line(p0, start));
+                }
+                closed = true;
+                p0 = start;
+            }
+        }
+    }
+    if !current.is_empty() {
+        result.push((current, closed));
+    }
+    result
+}
+
+/// Converts a `path` representing a stroked centerline into a closed outline
+/// suitable for filling. Closed subpaths produce two contours, an outer and
+/// an inner one; open subpaths produce a single contour capped at both ends.
+pub fn stroke_path(path: &BezPath, style: &StrokeStyle) -> BezPath {
+    let hw = style.width / 2.0;
+    let mut out = BezPath::new();
+
+    for (segs, closed) in subpaths(path) {
+        if segs.is_empty() {
+            continue;
+        }
+
+        let left: Vec<Seg> = segs.iter().map(|s| s.offset(hw)).collect();
+        let right: Vec<Seg> = segs.iter().map(|s| s.offset(-hw)).collect();
+        let rsegs: Vec<Seg> = right.iter().rev().map(Seg::reverse).collect();
+        let rorig: Vec<Seg> = segs.iter().rev().map(Seg::reverse).collect();
+
+        // `rorig`'s tangents are the forward ones negated and reshuffled, so
+        // a cross product taken from them directly would flip sign relative
+        // to the forward traversal. Reuse the forward turns, remapped to
+        // `rorig`'s index order, so both rails agree on which corners are
+        // convex.
+        let turn = forward_turns(&segs);
+        let rturn = reverse_turns(&turn);
+
+        if closed {
+            emit_contour(&mut out, &join_rail(&left, &segs, &turn, hw, style.join, true));
+            emit_contour(&mut out, &join_rail(&rsegs, &rorig, &rturn, -hw, style.join, true));
+        } else {
+            let mut contour = join_rail(&left, &segs, &turn, hw, style.join, false);
+
+            let end_pivot = segs.last().unwrap().end();
+            let end_tangent = segs.last().unwrap().tangent_end();
+            contour.extend(build_cap(
+                end_pivot,
+                end_tangent,
+                left.last().unwrap().end(),
+                right.last().unwrap().end(),
+                hw,
+                style.cap,
+            ));
+
+            contour.extend(join_rail(&rsegs, &rorig, &rturn, -hw, style.join, false));
+
+            let start_pivot = segs.first().unwrap().start();
+            let start_tangent = -segs.first().unwrap().tangent_start();
+            contour.extend(build_cap(
+                start_pivot,
+                start_tangent,
+                rsegs.last().unwrap().end(),
+                left.first().unwrap().start(),
+                hw,
+                style.cap,
+            ));
+
+            emit_contour(&mut out, &contour);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect_path(pts: [(f64, f64); 4]) -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(pts[0].0, pts[0].1));
+        for &(x, y) in &pts[1..] {
+            path.line_to(Point::new(x, y));
+        }
+        path.close_path();
+        path
+    }
+
+    fn has_point_near(path: &BezPath, target: Point, tol: f64) -> bool {
+        path.into_iter().any(|el| {
+            let p = match el {
+                PathEl::MoveTo(p) | PathEl::LineTo(p) => Some(p),
+                PathEl::QuadTo(_, p) => Some(p),
+                PathEl::CurveTo(_, _, p) => Some(p),
+                PathEl::ClosePath => None,
+            };
+            p.is_some_and(|p| (p - target).hypot() <= tol)
+        })
+    }
+
+    // Regression test for the review repro: stroking an axis-aligned square
+    // must put a sharp miter point at each outer corner and trim the inner
+    // corner to its true intersection, in either winding order.
+    #[test]
+    fn joins_on_square_both_windings() {
+        let style = StrokeStyle {
+            width: 10.0,
+            join: LineJoin::Miter(4.0),
+            cap: LineCap::Butt,
+        };
+
+        let cw = rect_path([(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]);
+        let outline = stroke_path(&cw, &style);
+        assert!(has_point_near(&outline, Point::new(105.0, -5.0), 1e-6));
+        assert!(has_point_near(&outline, Point::new(95.0, 5.0), 1e-6));
+
+        let ccw = rect_path([(0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0)]);
+        let outline = stroke_path(&ccw, &style);
+        assert!(has_point_near(&outline, Point::new(105.0, -5.0), 1e-6));
+        assert!(has_point_near(&outline, Point::new(95.0, 5.0), 1e-6));
+    }
+
+    #[test]
+    fn degenerate_segment_does_not_produce_nan() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.line_to(Point::new(0.0, 0.0));
+        path.line_to(Point::new(50.0, 0.0));
+
+        let style = StrokeStyle {
+            width: 4.0,
+            join: LineJoin::Round,
+            cap: LineCap::Round,
+        };
+        let outline = stroke_path(&path, &style);
+        for el in &outline {
+            let pts: Vec<Point> = match el {
+                PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+                PathEl::QuadTo(p1, p2) => vec![p1, p2],
+                PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+                PathEl::ClosePath => vec![],
+            };
+            for p in pts {
+                assert!(p.x.is_finite() && p.y.is_finite());
+            }
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! Conversion between SVG path `d` attribute strings and [`BezPath`].
+
+use kurbo::{BezPath, PathEl, Point, Vec2};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Cursor<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Cursor {
+            input: input.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b',')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eof(&mut self) -> bool {
+        self.skip_ws();
+        self.pos >= self.input.len()
+    }
+
+    fn more_args(&mut self) -> bool {
+        self.skip_ws();
+        matches!(self.peek(), Some(b'0'..=b'9' | b'-' | b'+' | b'.'))
+    }
+
+    fn read_command(&mut self) -> Option<u8> {
+        self.skip_ws();
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                self.pos += 1;
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+
+    fn read_number(&mut self) -> Result<f64, ParseError> {
+        self.skip_ws();
+        let start = self.pos;
+
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut saw_digit = false;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+            saw_digit = true;
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            while matches!(self.peek(), Some(b'0'..=b'9')) {
+                self.pos += 1;
+                saw_digit = true;
+            }
+        }
+        if !saw_digit {
+            return Err(ParseError {
+                message: "expected a number".to_string(),
+                position: start,
+            });
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if matches!(self.peek(), Some(b'0'..=b'9')) {
+                while matches!(self.peek(), Some(b'0'..=b'9')) {
+                    self.pos += 1;
+                }
+            } else {
+                // not actually an exponent, e.g. trailing bare 'e'
+                self.pos = mark;
+            }
+        }
+
+        let s = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        s.parse().map_err(|_| ParseError {
+            message: format!("invalid number '{s}'"),
+            position: start,
+        })
+    }
+
+    fn read_point(&mut self, relative: bool, current: Point) -> Result<Point, ParseError> {
+        let x = self.read_number()?;
+        let y = self.read_number()?;
+        Ok(if relative {
+            current + Vec2::new(x, y)
+        } else {
+            Point::new(x, y)
+        })
+    }
+
+    // Arc flags are a single 0/1 digit, never a general number: "1 0 1" and
+    // "101" are both valid and must parse the same way.
+    fn read_flag(&mut self) -> Result<bool, ParseError> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(ParseError {
+                message: "expected a flag ('0' or '1')".to_string(),
+                position: self.pos,
+            }),
+        }
+    }
+}
+
+/// Parses an SVG path `d` attribute into a [`BezPath`].
+///
+/// Supports all path commands (`M`/`L`/`H`/`V`/`C`/`S`/`Q`/`T`/`A`/`Z`) in
+/// both absolute and relative form; elliptical arcs are converted to cubics.
+pub fn parse_svg_path(d: &str) -> Result<BezPath, ParseError> {
+    let mut cur = Cursor::new(d);
+    let mut path = BezPath::new();
+
+    let mut current = Point::ORIGIN;
+    let mut subpath_start = Point::ORIGIN;
+    let mut last_cubic_ctrl: Option<Point> = None;
+    let mut last_quad_ctrl: Option<Point> = None;
+
+    while !cur.eof() {
+        let cmd = cur.read_command().ok_or_else(|| ParseError {
+            message: "expected a path command".to_string(),
+            position: cur.pos,
+        })?;
+        let relative = cmd.is_ascii_lowercase();
+        let mut upper = cmd.to_ascii_uppercase();
+
+        loop {
+            match upper {
+                b'M' => {
+                    let p = cur.read_point(relative, current)?;
+                    path.move_to(p);
+                    current = p;
+                    subpath_start = p;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                    // Extra coordinate pairs after a moveto are implicit linetos.
+                    upper = b'L';
+                }
+                b'L' => {
+                    let p = cur.read_point(relative, current)?;
+                    path.line_to(p);
+                    current = p;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                }
+                b'H' => {
+                    let x = cur.read_number()?;
+                    let p = Point::new(if relative { current.x + x } else { x }, current.y);
+                    path.line_to(p);
+                    current = p;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                }
+                b'V' => {
+                    let y = cur.read_number()?;
+                    let p = Point::new(current.x, if relative { current.y + y } else { y });
+                    path.line_to(p);
+                    current = p;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                }
+                b'C' => {
+                    let c1 = cur.read_point(relative, current)?;
+                    let c2 = cur.read_point(relative, current)?;
+                    let p = cur.read_point(relative, current)?;
+                    path.curve_to(c1, c2, p);
+                    current = p;
+                    last_cubic_ctrl = Some(c2);
+                    last_quad_ctrl = None;
+                }
+                b'S' => {
+                    let c1 = match last_cubic_ctrl {
+                        Some(prev) => current + (current - prev),
+                        None => current,
+                    };
+                    let c2 = cur.read_point(relative, current)?;
+                    let p = cur.read_point(relative, current)?;
+                    path.curve_to(c1, c2, p);
+                    current = p;
+                    last_cubic_ctrl = Some(c2);
+                    last_quad_ctrl = None;
+                }
+                b'Q' => {
+                    let c = cur.read_point(relative, current)?;
+                    let p = cur.read_point(relative, current)?;
+                    path.quad_to(c, p);
+                    current = p;
+                    last_quad_ctrl = Some(c);
+                    last_cubic_ctrl = None;
+                }
+                b'T' => {
+                    let c = match last_quad_ctrl {
+                        Some(prev) => current + (current - prev),
+                        None => current,
+                    };
+                    let p = cur.read_point(relative, current)?;
+                    path.quad_to(c, p);
+                    current = p;
+                    last_quad_ctrl = Some(c);
+                    last_cubic_ctrl = None;
+                }
+                b'A' => {
+                    let rx = cur.read_number()?;
+                    let ry = cur.read_number()?;
+                    let x_axis_rotation = cur.read_number()?;
+                    let large_arc = cur.read_flag()?;
+                    let sweep = cur.read_flag()?;
+                    let p = cur.read_point(relative, current)?;
+
+                    if rx == 0.0 || ry == 0.0 || current == p {
+                        path.line_to(p);
+                    } else {
+                        for (c1, c2, end) in
+                            arc_to_cubics(current, rx, ry, x_axis_rotation, large_arc, sweep, p)
+                        {
+                            path.curve_to(c1, c2, end);
+                        }
+                    }
+                    current = p;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                }
+                b'Z' => {
+                    path.close_path();
+                    current = subpath_start;
+                    last_cubic_ctrl = None;
+                    last_quad_ctrl = None;
+                }
+                _ => {
+                    return Err(ParseError {
+                        message: format!("unsupported command '{}'", cmd as char),
+                        position: cur.pos,
+                    })
+                }
+            }
+
+            if upper == b'Z' || !cur.more_args() {
+                break;
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+// Standard endpoint-to-center arc parameterization (SVG 1.1 appendix F.6),
+// split into segments of at most 90 degrees and approximated with cubics
+// via the usual `4/3 * tan(delta/4)` control-point distance.
+fn arc_to_cubics(
+    p0: Point,
+    mut rx: f64,
+    mut ry: f64,
+    x_axis_rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    p: Point,
+) -> Vec<(Point, Point, Point)> {
+    rx = rx.abs();
+    ry = ry.abs();
+    let phi = x_axis_rotation.to_radians();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+
+    let dx2 = (p0.x - p.x) / 2.0;
+    let dy2 = (p0.y - p.y) / 2.0;
+    let x1p = cos_phi * dx2 + sin_phi * dy2;
+    let y1p = -sin_phi * dx2 + cos_phi * dy2;
+
+    let lambda = (x1p / rx).powi(2) + (y1p / ry).powi(2);
+    if lambda > 1.0 {
+        let s = lambda.sqrt();
+        rx *= s;
+        ry *= s;
+    }
+
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let num = (rx * ry).powi(2) - (rx * y1p).powi(2) - (ry * x1p).powi(2);
+    let denom = (rx * y1p).powi(2) + (ry * x1p).powi(2);
+    let co = sign * (num / denom).max(0.0).sqrt();
+    let cxp = co * rx * y1p / ry;
+    let cyp = co * -ry * x1p / rx;
+
+    let cx = cos_phi * cxp - sin_phi * cyp + (p0.x + p.x) / 2.0;
+    let cy = sin_phi * cxp + cos_phi * cyp + (p0.y + p.y) / 2.0;
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| {
+        let dot = ux * vx + uy * vy;
+        let len = (ux * ux + uy * uy).sqrt() * (vx * vx + vy * vy).sqrt();
+        let mut a = (dot / len).clamp(-1.0, 1.0).acos();
+        if ux * vy - uy * vx < 0.0 {
+            a = -a;
+        }
+        a
+    };
+
+    let theta1 = angle(1.0, 0.0, (x1p - cxp) / rx, (y1p - cyp) / ry);
+    let mut delta = angle(
+        (x1p - cxp) / rx,
+        (y1p - cyp) / ry,
+        (-x1p - cxp) / rx,
+        (-y1p - cyp) / ry,
+    );
+    if !sweep && delta > 0.0 {
+        delta -= std::f64::consts::TAU;
+    } else if sweep && delta < 0.0 {
+        delta += std::f64::consts::TAU;
+    }
+
+    let segments = (delta.abs() / std::f64::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+    let segment_delta = delta / segments as f64;
+    let t = (4.0 / 3.0) * (segment_delta / 4.0).tan();
+
+    let mut cubics = Vec::with_capacity(segments);
+    let mut theta = theta1;
+    for _ in 0..segments {
+        let theta_end = theta + segment_delta;
+        let (sin_t, cos_t) = theta.sin_cos();
+        let (sin_e, cos_e) = theta_end.sin_cos();
+
+        let start = Point::new(
+            cx + rx * cos_t * cos_phi - ry * sin_t * sin_phi,
+            cy + rx * cos_t * sin_phi + ry * sin_t * cos_phi,
+        );
+        let end = Point::new(
+            cx + rx * cos_e * cos_phi - ry * sin_e * sin_phi,
+            cy + rx * cos_e * sin_phi + ry * sin_e * cos_phi,
+        );
+
+        let d_start = Vec2::new(
+            -rx * sin_t * cos_phi - ry * cos_t * sin_phi,
+            -rx * sin_t * sin_phi + ry * cos_t * cos_phi,
+        );
+        let d_end = Vec2::new(
+            -rx * sin_e * cos_phi - ry * cos_e * sin_phi,
+            -rx * sin_e * sin_phi + ry * cos_e * cos_phi,
+        );
+
+        cubics.push((start + d_start * t, end - d_end * t, end));
+        theta = theta_end;
+    }
+
+    cubics
+}
+
+/// Serializes a [`BezPath`] to a compact SVG path `d` attribute string.
+pub fn path_to_svg(path: &BezPath) -> String {
+    let mut out = String::new();
+    for el in path {
+        match el {
+            PathEl::MoveTo(p) => out.push_str(&format!("M{} {}", p.x, p.y)),
+            PathEl::LineTo(p) => out.push_str(&format!("L{} {}", p.x, p.y)),
+            PathEl::QuadTo(p1, p2) => {
+                out.push_str(&format!("Q{} {} {} {}", p1.x, p1.y, p2.x, p2.y))
+            }
+            PathEl::CurveTo(p1, p2, p3) => out.push_str(&format!(
+                "C{} {} {} {} {} {}",
+                p1.x, p1.y, p2.x, p2.y, p3.x, p3.y
+            )),
+            PathEl::ClosePath => out.push('Z'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kurbo::Point;
+
+    #[test]
+    fn parses_basic_commands_and_implicit_repetition() {
+        let path = parse_svg_path("M0 0 L10 0 20 10 Q30 0 40 10 Z").unwrap();
+        let els: Vec<_> = (&path).into_iter().collect();
+        assert_eq!(
+            els,
+            vec![
+                PathEl::MoveTo(Point::new(0.0, 0.0)),
+                PathEl::LineTo(Point::new(10.0, 0.0)),
+                PathEl::LineTo(Point::new(20.0, 10.0)),
+                PathEl::QuadTo(Point::new(30.0, 0.0), Point::new(40.0, 10.0)),
+                PathEl::ClosePath,
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_commands_are_offset_from_current_point() {
+        let path = parse_svg_path("M10 10 l5 5 h5 v5").unwrap();
+        let els: Vec<_> = (&path).into_iter().collect();
+        assert_eq!(
+            els,
+            vec![
+                PathEl::MoveTo(Point::new(10.0, 10.0)),
+                PathEl::LineTo(Point::new(15.0, 15.0)),
+                PathEl::LineTo(Point::new(20.0, 15.0)),
+                PathEl::LineTo(Point::new(20.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_command() {
+        let err = parse_svg_path("M0 0 X1 1").unwrap_err();
+        assert_eq!(err.position, 6);
+    }
+
+    #[test]
+    fn path_to_svg_roundtrips_through_parse() {
+        let mut path = BezPath::new();
+        path.move_to(Point::new(0.0, 0.0));
+        path.line_to(Point::new(10.0, 0.0));
+        path.curve_to(
+            Point::new(10.0, 5.0),
+            Point::new(5.0, 10.0),
+            Point::new(0.0, 10.0),
+        );
+        path.close_path();
+
+        let roundtripped = parse_svg_path(&path_to_svg(&path)).unwrap();
+        let original: Vec<_> = (&path).into_iter().collect();
+        let roundtripped: Vec<_> = (&roundtripped).into_iter().collect();
+        assert_eq!(original, roundtripped);
+    }
+}